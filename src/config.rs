@@ -0,0 +1,41 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::Result;
+use serde::Deserialize;
+
+/// Declares non-interactive export targets, so the tool can run unattended
+/// in cron/CI instead of prompting via `inquire`.
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    #[serde(rename = "target")]
+    pub targets: Vec<Target>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Target {
+    /// The named AWS profile to use, falling back to the default provider chain.
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    /// Zone names or hosted zone IDs to export.
+    pub zones: Vec<String>,
+    pub output: String,
+}
+
+/// Looks for `config.toml` in the current directory, the user's config
+/// directory, then the system config directory, in that order.
+pub fn discover() -> Option<PathBuf> {
+    let mut candidates = vec![PathBuf::from("config.toml")];
+
+    if let Some(dirs) = directories::ProjectDirs::from("", "", "route53-exporter") {
+        candidates.push(dirs.config_dir().join("config.toml"));
+    }
+
+    candidates.push(PathBuf::from("/etc/route53-exporter/config.toml"));
+
+    candidates.into_iter().find(|path| path.exists())
+}
+
+pub async fn load(path: &Path) -> Result<Config> {
+    let data = tokio::fs::read_to_string(path).await?;
+    Ok(toml::from_str(&data)?)
+}
@@ -0,0 +1,357 @@
+use aws_sdk_route53::types;
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostedZoneExport {
+    pub id: String,
+    pub name: String,
+    pub record_sets: Vec<ResourceRecordSet>,
+    pub dnssec: Dnssec,
+}
+
+impl HostedZoneExport {
+    pub fn new(
+        id: String,
+        name: String,
+        record_sets: Vec<types::ResourceRecordSet>,
+        dnssec: Dnssec,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            record_sets: record_sets.into_iter().map(Into::into).collect(),
+            dnssec,
+        }
+    }
+}
+
+/// DNSSEC signing status and key-signing keys for a zone, so the export is a
+/// complete snapshot suitable for audit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Dnssec {
+    pub status: Option<DnssecStatus>,
+    pub key_signing_keys: Vec<KeySigningKey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnssecStatus {
+    pub serve_signature: Option<String>,
+    pub status_message: Option<String>,
+}
+impl From<types::DnssecStatus> for DnssecStatus {
+    fn from(value: types::DnssecStatus) -> Self {
+        Self {
+            serve_signature: value.serve_signature,
+            status_message: value.status_message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeySigningKey {
+    pub name: Option<String>,
+    pub status: Option<String>,
+    pub flag: i32,
+    pub signing_algorithm_mnemonic: Option<String>,
+    pub signing_algorithm_type: i32,
+    pub key_tag: i32,
+    pub ds_record: Option<String>,
+    pub public_key: Option<String>,
+}
+impl From<types::KeySigningKey> for KeySigningKey {
+    fn from(value: types::KeySigningKey) -> Self {
+        Self {
+            name: value.name,
+            status: value.status,
+            flag: value.flag,
+            signing_algorithm_mnemonic: value.signing_algorithm_mnemonic,
+            signing_algorithm_type: value.signing_algorithm_type,
+            key_tag: value.key_tag,
+            ds_record: value.ds_record,
+            public_key: value.public_key,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceRecordSet {
+    pub name: String,
+    pub r#type: String,
+    pub set_identifier: Option<String>,
+    pub weight: Option<i64>,
+    pub region: Option<String>,
+    pub geo_location: Option<GeoLocation>,
+    pub failover: Option<String>,
+    pub multi_value_answer: Option<bool>,
+    pub ttl: Option<i64>,
+    pub resource_records: Option<Vec<ResourceRecord>>,
+    pub alias_target: Option<AliasTarget>,
+    pub health_check_id: Option<String>,
+    pub traffic_policy_instance_id: Option<String>,
+    pub cidr_routing_config: Option<CidrRoutingConfig>,
+    pub geo_proximity_location: Option<GeoProximityLocation>,
+}
+impl From<types::ResourceRecordSet> for ResourceRecordSet {
+    fn from(value: types::ResourceRecordSet) -> Self {
+        Self {
+            name: value.name,
+            r#type: value.r#type.as_str().to_owned(),
+            set_identifier: value.set_identifier,
+            weight: value.weight,
+            region: value.region.map(|r| r.as_str().to_owned()),
+            geo_location: value.geo_location.map(Into::into),
+            failover: value.failover.map(|f| f.as_str().to_owned()),
+            multi_value_answer: value.multi_value_answer,
+            ttl: value.ttl,
+            resource_records: value
+                .resource_records
+                .map(|records| records.into_iter().map(Into::into).collect()),
+            alias_target: value.alias_target.map(Into::into),
+            health_check_id: value.health_check_id,
+            traffic_policy_instance_id: value.traffic_policy_instance_id,
+            cidr_routing_config: value.cidr_routing_config.map(Into::into),
+            geo_proximity_location: value.geo_proximity_location.map(Into::into),
+        }
+    }
+}
+impl TryFrom<&ResourceRecordSet> for types::ResourceRecordSet {
+    type Error = color_eyre::Report;
+
+    /// Rebuilds the AWS record set this was exported from, for the `import`
+    /// subcommand. A hand-edited or partial export can be missing fields
+    /// the AWS builders require, so this returns an error instead of
+    /// panicking.
+    fn try_from(value: &ResourceRecordSet) -> Result<Self> {
+        let resource_records = value
+            .resource_records
+            .as_ref()
+            .map(|records| {
+                records
+                    .iter()
+                    .map(types::ResourceRecord::try_from)
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+        let alias_target = value
+            .alias_target
+            .as_ref()
+            .map(types::AliasTarget::try_from)
+            .transpose()?;
+        let cidr_routing_config = value
+            .cidr_routing_config
+            .as_ref()
+            .map(types::CidrRoutingConfig::try_from)
+            .transpose()?;
+        let geo_proximity_location = value
+            .geo_proximity_location
+            .as_ref()
+            .map(types::GeoProximityLocation::try_from)
+            .transpose()?;
+
+        types::ResourceRecordSet::builder()
+            .name(&value.name)
+            .r#type(types::RrType::from(value.r#type.as_str()))
+            .set_identifier(value.set_identifier.clone())
+            .set_weight(value.weight)
+            .set_region(
+                value
+                    .region
+                    .as_deref()
+                    .map(types::ResourceRecordSetRegion::from),
+            )
+            .set_geo_location(value.geo_location.as_ref().map(Into::into))
+            .set_failover(
+                value
+                    .failover
+                    .as_deref()
+                    .map(types::ResourceRecordSetFailover::from),
+            )
+            .set_multi_value_answer(value.multi_value_answer)
+            .set_ttl(value.ttl)
+            .set_resource_records(resource_records)
+            .set_alias_target(alias_target)
+            .set_health_check_id(value.health_check_id.clone())
+            .set_traffic_policy_instance_id(value.traffic_policy_instance_id.clone())
+            .set_cidr_routing_config(cidr_routing_config)
+            .set_geo_proximity_location(geo_proximity_location)
+            .build()
+            .map_err(|err| eyre!("record set {:?} is missing a required field: {err}", value.name))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeoLocation {
+    pub continent_code: Option<String>,
+    pub country_code: Option<String>,
+    pub subdivision_code: Option<String>,
+    /// Populated from `--geoip-db`, otherwise left as `None`.
+    #[serde(default)]
+    pub continent_name: Option<String>,
+    #[serde(default)]
+    pub country_name: Option<String>,
+    #[serde(default)]
+    pub subdivision_name: Option<String>,
+}
+impl From<types::GeoLocation> for GeoLocation {
+    fn from(value: types::GeoLocation) -> Self {
+        Self {
+            continent_code: value.continent_code,
+            country_code: value.country_code,
+            subdivision_code: value.subdivision_code,
+            continent_name: None,
+            country_name: None,
+            subdivision_name: None,
+        }
+    }
+}
+impl From<&GeoLocation> for types::GeoLocation {
+    fn from(value: &GeoLocation) -> Self {
+        types::GeoLocation::builder()
+            .set_continent_code(value.continent_code.clone())
+            .set_country_code(value.country_code.clone())
+            .set_subdivision_code(value.subdivision_code.clone())
+            .build()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceRecord {
+    pub value: String,
+}
+impl From<types::ResourceRecord> for ResourceRecord {
+    fn from(value: types::ResourceRecord) -> Self {
+        Self { value: value.value }
+    }
+}
+impl TryFrom<&ResourceRecord> for types::ResourceRecord {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: &ResourceRecord) -> Result<Self> {
+        types::ResourceRecord::builder()
+            .value(&value.value)
+            .build()
+            .map_err(|err| eyre!("resource record is missing a value: {err}"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AliasTarget {
+    pub hosted_zone_id: String,
+    pub dns_name: String,
+    pub evaluate_target_health: bool,
+}
+impl From<types::AliasTarget> for AliasTarget {
+    fn from(value: types::AliasTarget) -> Self {
+        Self {
+            hosted_zone_id: value.hosted_zone_id,
+            dns_name: value.dns_name,
+            evaluate_target_health: value.evaluate_target_health,
+        }
+    }
+}
+impl TryFrom<&AliasTarget> for types::AliasTarget {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: &AliasTarget) -> Result<Self> {
+        types::AliasTarget::builder()
+            .hosted_zone_id(&value.hosted_zone_id)
+            .dns_name(&value.dns_name)
+            .evaluate_target_health(value.evaluate_target_health)
+            .build()
+            .map_err(|err| eyre!("alias target for {:?} is missing a required field: {err}", value.dns_name))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CidrRoutingConfig {
+    pub collection_id: String,
+    pub location_name: String,
+}
+impl From<types::CidrRoutingConfig> for CidrRoutingConfig {
+    fn from(value: types::CidrRoutingConfig) -> Self {
+        Self {
+            collection_id: value.collection_id,
+            location_name: value.location_name,
+        }
+    }
+}
+impl TryFrom<&CidrRoutingConfig> for types::CidrRoutingConfig {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: &CidrRoutingConfig) -> Result<Self> {
+        types::CidrRoutingConfig::builder()
+            .collection_id(&value.collection_id)
+            .location_name(&value.location_name)
+            .build()
+            .map_err(|err| eyre!("CIDR routing config is missing a required field: {err}"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeoProximityLocation {
+    pub aws_region: Option<String>,
+    pub local_zone_group: Option<String>,
+    pub coordinates: Option<Coordinates>,
+    pub bias: Option<i32>,
+}
+impl From<types::GeoProximityLocation> for GeoProximityLocation {
+    fn from(value: types::GeoProximityLocation) -> Self {
+        Self {
+            aws_region: value.aws_region,
+            local_zone_group: value.local_zone_group,
+            coordinates: value.coordinates.map(Into::into),
+            bias: value.bias,
+        }
+    }
+}
+impl TryFrom<&GeoProximityLocation> for types::GeoProximityLocation {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: &GeoProximityLocation) -> Result<Self> {
+        let coordinates = value
+            .coordinates
+            .as_ref()
+            .map(types::Coordinates::try_from)
+            .transpose()?;
+
+        Ok(types::GeoProximityLocation::builder()
+            .set_aws_region(value.aws_region.clone())
+            .set_local_zone_group(value.local_zone_group.clone())
+            .set_coordinates(coordinates)
+            .set_bias(value.bias)
+            .build())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Coordinates {
+    pub latitude: String,
+    pub longitude: String,
+    /// Nearest known city/country, populated from `--geoip-db`.
+    #[serde(default)]
+    pub city_name: Option<String>,
+    #[serde(default)]
+    pub country_name: Option<String>,
+}
+impl From<types::Coordinates> for Coordinates {
+    fn from(value: types::Coordinates) -> Self {
+        Self {
+            latitude: value.latitude,
+            longitude: value.longitude,
+            city_name: None,
+            country_name: None,
+        }
+    }
+}
+impl TryFrom<&Coordinates> for types::Coordinates {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: &Coordinates) -> Result<Self> {
+        types::Coordinates::builder()
+            .latitude(&value.latitude)
+            .longitude(&value.longitude)
+            .build()
+            .map_err(|err| eyre!("geo-proximity coordinates are missing a required field: {err}"))
+    }
+}
@@ -0,0 +1,238 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use color_eyre::Result;
+use maxminddb::{geoip2, Reader};
+
+use crate::export::{Coordinates, GeoLocation, GeoProximityLocation, HostedZoneExport};
+
+/// Wraps a local MaxMind GeoLite2/GeoIP2 City database so geo-routing records
+/// can be enriched with human-readable names instead of bare ISO codes.
+///
+/// The database is IP-keyed, with no reverse index from a continent/country/
+/// subdivision code (or a coordinate pair) back to a name, so resolving one
+/// means scanning the database's networks until a match turns up. Real
+/// exports only ever reference a handful of distinct codes/coordinates, so
+/// we resolve lazily per distinct value and cache the result, rather than
+/// eagerly walking every network in the database up front regardless of
+/// whether anything in the export needs it.
+pub struct GeoIpDb {
+    reader: Reader<Vec<u8>>,
+    continent_names: RefCell<HashMap<String, Option<String>>>,
+    country_names: RefCell<HashMap<String, Option<String>>>,
+    /// Subdivision ISO codes are only unique within a country (e.g. `CA` is
+    /// both a Canadian province and a US state), so they're keyed together
+    /// with the owning country code.
+    subdivision_names: RefCell<HashMap<(String, String), Option<String>>>,
+    nearest_city: RefCell<HashMap<(String, String), Option<(String, String)>>>,
+}
+
+impl GeoIpDb {
+    pub fn open(path: &str) -> Result<Self> {
+        let reader = Reader::open_readfile(path)?;
+
+        Ok(Self {
+            reader,
+            continent_names: RefCell::new(HashMap::new()),
+            country_names: RefCell::new(HashMap::new()),
+            subdivision_names: RefCell::new(HashMap::new()),
+            nearest_city: RefCell::new(HashMap::new()),
+        })
+    }
+
+    pub fn enrich_export(&self, export: &mut HostedZoneExport) {
+        for record in &mut export.record_sets {
+            if let Some(geo) = &mut record.geo_location {
+                self.enrich_geo_location(geo);
+            }
+            if let Some(proximity) = &mut record.geo_proximity_location {
+                self.enrich_geo_proximity(proximity);
+            }
+        }
+    }
+
+    fn enrich_geo_location(&self, geo: &mut GeoLocation) {
+        geo.continent_name = geo
+            .continent_code
+            .as_deref()
+            .and_then(|code| self.continent_name(code));
+        geo.country_name = geo
+            .country_code
+            .as_deref()
+            .and_then(|code| self.country_name(code));
+        geo.subdivision_name = match (&geo.country_code, &geo.subdivision_code) {
+            (Some(country), Some(subdivision)) => self.subdivision_name(country, subdivision),
+            _ => None,
+        };
+    }
+
+    fn enrich_geo_proximity(&self, proximity: &mut GeoProximityLocation) {
+        if let Some(coordinates) = &mut proximity.coordinates {
+            self.enrich_coordinates(coordinates);
+        }
+    }
+
+    fn enrich_coordinates(&self, coordinates: &mut Coordinates) {
+        let Some((city_name, country_name)) =
+            self.nearest_city(&coordinates.latitude, &coordinates.longitude)
+        else {
+            return;
+        };
+
+        coordinates.city_name = Some(city_name);
+        coordinates.country_name = Some(country_name);
+    }
+
+    fn continent_name(&self, code: &str) -> Option<String> {
+        if let Some(cached) = self.continent_names.borrow().get(code) {
+            return cached.clone();
+        }
+
+        let name = self.scan(|record| {
+            let continent = record.continent.as_ref()?;
+            if continent.code? != code {
+                return None;
+            }
+            english_name(&continent.names)
+        });
+
+        self.continent_names
+            .borrow_mut()
+            .insert(code.to_owned(), name.clone());
+        name
+    }
+
+    fn country_name(&self, code: &str) -> Option<String> {
+        if let Some(cached) = self.country_names.borrow().get(code) {
+            return cached.clone();
+        }
+
+        let name = self.scan(|record| {
+            let country = record.country.as_ref()?;
+            if country.iso_code? != code {
+                return None;
+            }
+            english_name(&country.names)
+        });
+
+        self.country_names
+            .borrow_mut()
+            .insert(code.to_owned(), name.clone());
+        name
+    }
+
+    fn subdivision_name(&self, country_code: &str, subdivision_code: &str) -> Option<String> {
+        let key = (country_code.to_owned(), subdivision_code.to_owned());
+        if let Some(cached) = self.subdivision_names.borrow().get(&key) {
+            return cached.clone();
+        }
+
+        let name = self.scan(|record| {
+            let country = record.country.as_ref()?;
+            if country.iso_code? != country_code {
+                return None;
+            }
+            record.subdivisions.as_ref()?.iter().find_map(|subdivision| {
+                if subdivision.iso_code? != subdivision_code {
+                    return None;
+                }
+                english_name(&subdivision.names)
+            })
+        });
+
+        self.subdivision_names.borrow_mut().insert(key, name.clone());
+        name
+    }
+
+    /// Streams every City record in the database, looking for the first one
+    /// for which `find` returns `Some`, without buffering the records seen
+    /// along the way.
+    fn scan(&self, mut find: impl FnMut(&geoip2::City) -> Option<String>) -> Option<String> {
+        for net in [
+            ipnet::IpNet::from((Ipv4Addr::UNSPECIFIED.into(), 0)),
+            ipnet::IpNet::from((Ipv6Addr::UNSPECIFIED.into(), 0)),
+        ] {
+            let Ok(within) = self.reader.within::<geoip2::City>(net) else {
+                continue;
+            };
+
+            for item in within.flatten() {
+                if let Some(name) = find(&item.info) {
+                    return Some(name);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn nearest_city(&self, latitude: &str, longitude: &str) -> Option<(String, String)> {
+        let key = (latitude.to_owned(), longitude.to_owned());
+        if let Some(cached) = self.nearest_city.borrow().get(&key) {
+            return cached.clone();
+        }
+
+        let lat = latitude.parse::<f64>().ok();
+        let lon = longitude.parse::<f64>().ok();
+        let nearest = lat.zip(lon).and_then(|(lat, lon)| self.scan_nearest_city(lat, lon));
+
+        self.nearest_city.borrow_mut().insert(key, nearest.clone());
+        nearest
+    }
+
+    /// Single pass over the database tracking the closest city seen so far,
+    /// rather than collecting every city into a `Vec` up front and scanning
+    /// it afterwards.
+    fn scan_nearest_city(&self, lat: f64, lon: f64) -> Option<(String, String)> {
+        let mut best: Option<(f64, String, String)> = None;
+
+        for net in [
+            ipnet::IpNet::from((Ipv4Addr::UNSPECIFIED.into(), 0)),
+            ipnet::IpNet::from((Ipv6Addr::UNSPECIFIED.into(), 0)),
+        ] {
+            let Ok(within) = self.reader.within::<geoip2::City>(net) else {
+                continue;
+            };
+
+            for item in within.flatten() {
+                let record = item.info;
+                let (Some(location), Some(city), Some(country)) =
+                    (&record.location, &record.city, &record.country)
+                else {
+                    continue;
+                };
+                let (Some(city_lat), Some(city_lon), Some(city_name), Some(country_name)) = (
+                    location.latitude,
+                    location.longitude,
+                    english_name(&city.names),
+                    english_name(&country.names),
+                ) else {
+                    continue;
+                };
+
+                let distance = squared_distance(lat, lon, city_lat, city_lon);
+                let is_closer = match &best {
+                    Some((best_distance, _, _)) => distance < *best_distance,
+                    None => true,
+                };
+                if is_closer {
+                    best = Some((distance, city_name, country_name));
+                }
+            }
+        }
+
+        best.map(|(_, city_name, country_name)| (city_name, country_name))
+    }
+}
+
+fn squared_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    (lat1 - lat2).powi(2) + (lon1 - lon2).powi(2)
+}
+
+fn english_name(names: &Option<HashMap<&str, &str>>) -> Option<String> {
+    names
+        .as_ref()
+        .and_then(|names| names.get("en"))
+        .map(|name| (*name).to_owned())
+}
@@ -0,0 +1,232 @@
+use aws_sdk_route53::types;
+use color_eyre::{eyre::eyre, Result};
+use tokio::fs;
+
+use crate::export::HostedZoneExport;
+
+/// Route53 caps a single `ChangeResourceRecordSets` request at 1000 changes and
+/// roughly 32000 characters across all record values.
+const MAX_CHANGES_PER_BATCH: usize = 1000;
+const MAX_BATCH_VALUE_CHARS: usize = 32_000;
+
+pub struct ImportOptions {
+    pub hosted_zone_id: String,
+    pub force: bool,
+    pub dry_run: bool,
+}
+
+pub async fn run(
+    client: &aws_sdk_route53::Client,
+    input: &str,
+    options: ImportOptions,
+) -> Result<()> {
+    let export = read_export(input, &options.hosted_zone_id).await?;
+
+    let changes: Vec<types::Change> = export
+        .record_sets
+        .iter()
+        .filter(|record| options.force || !is_apex_ns_or_soa(record, &export.name))
+        .map(|record| {
+            let resource_record_set = types::ResourceRecordSet::try_from(record)?;
+            types::Change::builder()
+                .action(types::ChangeAction::Upsert)
+                .resource_record_set(resource_record_set)
+                .build()
+                .map_err(|err| eyre!("could not build a change for {:?}: {err}", record.name))
+        })
+        .collect::<Result<_>>()?;
+
+    let skipped = export.record_sets.len() - changes.len();
+    if skipped > 0 {
+        println!("Skipping {skipped} auto-managed NS/SOA record(s) at the zone apex (use --force to include them)");
+    }
+
+    let batches = chunk_changes(changes);
+    println!(
+        "Importing {} record set(s) into {} across {} batch(es)",
+        export.record_sets.len() - skipped,
+        export.name,
+        batches.len()
+    );
+
+    for (i, batch) in batches.into_iter().enumerate() {
+        if options.dry_run {
+            println!("--- dry run: batch {} of {} changes ---", i + 1, batch.len());
+            for change in &batch {
+                let rrs = change.resource_record_set();
+                println!(
+                    "  UPSERT {} {} ({})",
+                    rrs.map(|r| r.name()).unwrap_or_default(),
+                    rrs.map(|r| r.r#type().as_str()).unwrap_or_default(),
+                    rrs.and_then(|r| r.set_identifier()).unwrap_or("-"),
+                );
+            }
+            continue;
+        }
+
+        let change_batch = types::ChangeBatch::builder()
+            .set_changes(Some(batch))
+            .build()?;
+
+        let response = client
+            .change_resource_record_sets()
+            .hosted_zone_id(&options.hosted_zone_id)
+            .change_batch(change_batch)
+            .send()
+            .await?;
+
+        let change_id = response
+            .change_info()
+            .map(|info| info.id().to_owned())
+            .ok_or_else(|| eyre!("Route53 did not return a change id for batch {}", i + 1))?;
+
+        wait_for_insync(client, &change_id).await?;
+        println!("Batch {} of changes is INSYNC", i + 1);
+    }
+
+    Ok(())
+}
+
+async fn read_export(input: &str, hosted_zone_id: &str) -> Result<HostedZoneExport> {
+    let data = fs::read_to_string(input).await?;
+
+    let exports: Vec<HostedZoneExport> = match serde_json::from_str::<Vec<HostedZoneExport>>(&data)
+    {
+        Ok(exports) => exports,
+        Err(_) => vec![serde_json::from_str::<HostedZoneExport>(&data)?],
+    };
+
+    if exports.len() == 1 {
+        return Ok(exports.into_iter().next().expect("checked len == 1"));
+    }
+
+    exports
+        .into_iter()
+        .find(|export| strip_hosted_zone_prefix(&export.id) == strip_hosted_zone_prefix(hosted_zone_id))
+        .ok_or_else(|| eyre!("no hosted zone matching {hosted_zone_id} found in {input}"))
+}
+
+fn strip_hosted_zone_prefix(id: &str) -> &str {
+    id.trim_start_matches("/hostedzone/")
+}
+
+fn is_apex_ns_or_soa(record: &crate::export::ResourceRecordSet, zone_name: &str) -> bool {
+    matches!(record.r#type.as_str(), "NS" | "SOA") && record.name.trim_end_matches('.') == zone_name.trim_end_matches('.')
+}
+
+fn chunk_changes(changes: Vec<types::Change>) -> Vec<Vec<types::Change>> {
+    let mut batches = Vec::new();
+    let mut current = Vec::new();
+    let mut current_chars = 0usize;
+
+    for change in changes {
+        let change_chars = change_value_chars(&change);
+
+        if !current.is_empty()
+            && (current.len() >= MAX_CHANGES_PER_BATCH
+                || current_chars + change_chars > MAX_BATCH_VALUE_CHARS)
+        {
+            batches.push(std::mem::take(&mut current));
+            current_chars = 0;
+        }
+
+        current_chars += change_chars;
+        current.push(change);
+    }
+
+    if !current.is_empty() {
+        batches.push(current);
+    }
+
+    batches
+}
+
+fn change_value_chars(change: &types::Change) -> usize {
+    change
+        .resource_record_set()
+        .and_then(|rrs| rrs.resource_records())
+        .map(|records| records.iter().map(|r| r.value().len()).sum())
+        .unwrap_or(0)
+}
+
+async fn wait_for_insync(client: &aws_sdk_route53::Client, change_id: &str) -> Result<()> {
+    loop {
+        let response = client.get_change().id(change_id).send().await?;
+        let status = response
+            .change_info()
+            .map(|info| info.status().clone())
+            .ok_or_else(|| eyre!("GetChange returned no status for {change_id}"))?;
+
+        if status == types::ChangeStatus::Insync {
+            return Ok(());
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(value_len: usize) -> types::Change {
+        let record_set = types::ResourceRecordSet::builder()
+            .name("example.com.")
+            .r#type(types::RrType::Txt)
+            .ttl(300)
+            .resource_records(
+                types::ResourceRecord::builder()
+                    .value("x".repeat(value_len))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        types::Change::builder()
+            .action(types::ChangeAction::Upsert)
+            .resource_record_set(record_set)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn chunk_changes_splits_on_max_changes_per_batch() {
+        let changes = (0..MAX_CHANGES_PER_BATCH + 1).map(|_| change(1)).collect();
+
+        let batches = chunk_changes(changes);
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), MAX_CHANGES_PER_BATCH);
+        assert_eq!(batches[1].len(), 1);
+    }
+
+    #[test]
+    fn chunk_changes_splits_on_max_batch_value_chars() {
+        let changes = vec![change(MAX_BATCH_VALUE_CHARS), change(1)];
+
+        let batches = chunk_changes(changes);
+
+        assert_eq!(batches.len(), 2);
+    }
+
+    #[test]
+    fn chunk_changes_keeps_a_single_oversized_change_in_its_own_batch() {
+        let changes = vec![change(MAX_BATCH_VALUE_CHARS + 1)];
+
+        let batches = chunk_changes(changes);
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 1);
+    }
+
+    #[test]
+    fn chunk_changes_packs_small_changes_into_one_batch() {
+        let changes = vec![change(1), change(1), change(1)];
+
+        let batches = chunk_changes(changes);
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 3);
+    }
+}
@@ -2,17 +2,40 @@ use std::fmt::Display;
 
 use aws_config::{meta::region::RegionProviderChain, BehaviorVersion};
 use aws_sdk_route53::{config::Region, meta::PKG_VERSION, types, Client};
-use clap::Parser;
-use color_eyre::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use color_eyre::{eyre::eyre, Result};
 use inquire::Select;
 use tokio::fs;
 
 use crate::export::HostedZoneExport;
+use crate::import::ImportOptions;
 
-/// Export Route53 Hosted Zones from a specific region.
+mod config;
+mod export;
+mod geoip;
+mod import;
+mod s3;
+mod table;
+mod zonefile;
+
+/// Export Route53 Hosted Zones to a file, or import one back into a zone.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Export Route53 Hosted Zones from a specific region.
+    Export(ExportArgs),
+    /// Restore a previously exported Hosted Zone back into Route53.
+    Import(ImportArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct ExportArgs {
     /// The AWS Region
     #[arg(short, long)]
     region: Option<String>,
@@ -20,42 +43,252 @@ struct Args {
     /// The export filename
     #[arg(short, long, default_value = "route53-export.json")]
     export: String,
+
+    /// The export file format
+    #[arg(short, long, value_enum, default_value_t = Format::Json)]
+    format: Format,
+
+    /// Error out when a config-file zone can't be found, instead of warning
+    /// and skipping it. Only takes effect when a `config.toml` is discovered.
+    #[arg(long)]
+    non_interactive: bool,
+
+    /// Path to a MaxMind GeoLite2/GeoIP2 `.mmdb` file to enrich geo-routing
+    /// records with human-readable continent/country/subdivision names.
+    #[arg(long)]
+    geoip_db: Option<String>,
+
+    /// Gzip-compress the payload before uploading to S3 (appends `.gz`).
+    /// Only takes effect when the destination (`--export`, or a config
+    /// target's `output`) is an `s3://bucket/prefix` URL.
+    #[arg(long)]
+    compress: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum Format {
+    Json,
+    Zonefile,
+    Csv,
+}
+
+#[derive(clap::Args, Debug)]
+struct ImportArgs {
+    /// The AWS Region
+    #[arg(short, long)]
+    region: Option<String>,
+
+    /// The exported JSON file to import
+    #[arg(short, long)]
+    input: String,
+
+    /// The Hosted Zone ID to import records into
+    #[arg(short = 'z', long)]
+    hosted_zone_id: String,
+
+    /// Also apply changes to the auto-managed NS/SOA records at the zone apex
+    #[arg(long)]
+    force: bool,
+
+    /// Print the planned changes without submitting them
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
-    let Args { region, export } = Args::parse();
+    let Args { command } = Args::parse();
+
+    match command {
+        Command::Export(args) => run_export(args).await,
+        Command::Import(args) => run_import(args).await,
+    }
+}
+
+async fn client_for_region(region: Option<String>) -> (Client, aws_config::SdkConfig) {
+    build_client(None, region).await
+}
 
+async fn build_client(profile: Option<&str>, region: Option<String>) -> (Client, aws_config::SdkConfig) {
     let region = RegionProviderChain::first_try(region.map(Region::new))
         .or_default_provider()
         .or_else("us-east-1");
-    let config = aws_config::defaults(BehaviorVersion::latest())
-        .region(region)
-        .load()
-        .await;
+    let mut loader = aws_config::defaults(BehaviorVersion::latest()).region(region);
+    if let Some(profile) = profile {
+        loader = loader.profile_name(profile);
+    }
+    let config = loader.load().await;
 
     println!("Route53 client version: {}", PKG_VERSION);
-    let client = Client::new(&config);
+    (Client::new(&config), config)
+}
+
+async fn run_export(args: ExportArgs) -> Result<()> {
+    let ExportArgs {
+        region,
+        export,
+        format,
+        non_interactive,
+        geoip_db,
+        compress,
+    } = args;
+
+    let geoip_db = geoip_db.map(|path| geoip::GeoIpDb::open(&path)).transpose()?;
+
+    if let Some(config_path) = config::discover() {
+        return run_export_from_config(
+            &config_path,
+            format,
+            non_interactive,
+            geoip_db.as_ref(),
+            compress,
+        )
+        .await;
+    }
+
+    let (client, aws_config) = client_for_region(region).await;
 
     let hz = get_hosted_zone(&client).await?;
 
-    let export_data: String = match hz {
+    let exports = match hz {
         HZOption::All(hosted_zones) => {
             let mut exports = Vec::with_capacity(hosted_zones.len());
             for hz in hosted_zones {
-                exports.push(get_export_data(&client, hz).await?);
+                exports.push(get_export_data(&client, hz, geoip_db.as_ref()).await?);
             }
-            serde_json::to_string_pretty(&exports)?
-        }
-        HZOption::HZ(hz) => {
-            let export = get_export_data(&client, hz).await?;
-            serde_json::to_string_pretty(&export)?
+            exports
         }
+        HZOption::HZ(hz) => vec![get_export_data(&client, hz, geoip_db.as_ref()).await?],
     };
 
-    fs::write(&export, export_data).await?;
-    println!("Successfully exported data to {export} 🎉");
+    table::print_summary(&exports);
+    write_exports(&aws_config, &export, &exports, format, compress).await
+}
+
+/// Writes exports either to a local path or, when `destination` parses as an
+/// `s3://bucket/prefix` URL, uploads one object per zone to S3.
+async fn write_exports(
+    aws_config: &aws_config::SdkConfig,
+    destination: &str,
+    exports: &[HostedZoneExport],
+    format: Format,
+    compress: bool,
+) -> Result<()> {
+    let extension = match format {
+        Format::Json => "json",
+        Format::Zonefile => "zone",
+        Format::Csv => "csv",
+    };
+
+    if let Some(s3_destination) = s3::S3Destination::parse(destination) {
+        for export in exports {
+            let export_data = render_exports(std::slice::from_ref(export), format)?;
+            s3::upload(
+                aws_config,
+                &s3_destination,
+                &export.name,
+                export_data,
+                extension,
+                compress,
+            )
+            .await?;
+        }
+        return Ok(());
+    }
+
+    let export_data = render_exports(exports, format)?;
+    fs::write(destination, export_data).await?;
+    println!("Successfully exported data to {destination} 🎉");
+
+    Ok(())
+}
+
+async fn run_export_from_config(
+    config_path: &std::path::Path,
+    format: Format,
+    non_interactive: bool,
+    geoip_db: Option<&geoip::GeoIpDb>,
+    compress: bool,
+) -> Result<()> {
+    let config = config::load(config_path).await?;
+    println!("Using config file at {}", config_path.display());
+
+    for target in config.targets {
+        let (client, aws_config) =
+            build_client(target.profile.as_deref(), target.region.clone()).await;
+        let hosted_zones = list_all_hosted_zones(&client).await?;
+
+        let mut exports = Vec::with_capacity(target.zones.len());
+        for wanted in &target.zones {
+            match find_hosted_zone(&hosted_zones, wanted) {
+                Some(hz) => exports.push(get_export_data(&client, hz.to_owned(), geoip_db).await?),
+                None if non_interactive => {
+                    return Err(eyre!("hosted zone {wanted} not found in account/region"));
+                }
+                None => {
+                    eprintln!("Warning: hosted zone {wanted} not found, skipping");
+                }
+            }
+        }
+
+        table::print_summary(&exports);
+        write_exports(&aws_config, &target.output, &exports, format, compress).await?;
+    }
+
+    Ok(())
+}
+
+fn find_hosted_zone<'a>(
+    hosted_zones: &'a [types::HostedZone],
+    wanted: &str,
+) -> Option<&'a types::HostedZone> {
+    hosted_zones.iter().find(|hz| {
+        hz.id.trim_start_matches("/hostedzone/") == wanted.trim_start_matches("/hostedzone/")
+            || hz.name.trim_end_matches('.') == wanted.trim_end_matches('.')
+    })
+}
+
+fn render_exports(exports: &[HostedZoneExport], format: Format) -> Result<String> {
+    match format {
+        Format::Json => Ok(if exports.len() == 1 {
+            serde_json::to_string_pretty(&exports[0])?
+        } else {
+            serde_json::to_string_pretty(exports)?
+        }),
+        Format::Zonefile => Ok(exports
+            .iter()
+            .map(zonefile::render)
+            .collect::<Vec<_>>()
+            .join("\n")),
+        Format::Csv => table::render_csv(exports),
+    }
+}
+
+async fn run_import(args: ImportArgs) -> Result<()> {
+    let ImportArgs {
+        region,
+        input,
+        hosted_zone_id,
+        force,
+        dry_run,
+    } = args;
+    let (client, _aws_config) = client_for_region(region).await;
+
+    import::run(
+        &client,
+        &input,
+        ImportOptions {
+            hosted_zone_id,
+            force,
+            dry_run,
+        },
+    )
+    .await?;
+
+    if !dry_run {
+        println!("Successfully imported data from {input} 🎉");
+    }
 
     Ok(())
 }
@@ -83,8 +316,7 @@ async fn get_hosted_zone(client: &aws_sdk_route53::Client) -> Result<HZOption> {
         hosted_zone_count.hosted_zone_count(),
     );
 
-    let hosted_zones = client.list_hosted_zones().send().await?;
-    let hosted_zones = hosted_zones.hosted_zones().to_owned();
+    let hosted_zones = list_all_hosted_zones(client).await?;
 
     let mut options: Vec<HZOption> = hosted_zones.iter().cloned().map(HZOption::HZ).collect();
     options.insert(0, HZOption::All(hosted_zones));
@@ -101,168 +333,109 @@ async fn get_hosted_zone(client: &aws_sdk_route53::Client) -> Result<HZOption> {
 async fn get_export_data(
     client: &aws_sdk_route53::Client,
     hz: types::HostedZone,
+    geoip_db: Option<&geoip::GeoIpDb>,
 ) -> Result<HostedZoneExport> {
-    let records = client
-        .list_resource_record_sets()
-        .hosted_zone_id(hz.id())
-        .send()
-        .await?;
+    let record_sets = list_all_resource_record_sets(client, hz.id()).await?;
+    let dnssec = get_dnssec(client, hz.id()).await;
 
-    Ok(HostedZoneExport::new(
-        hz.id,
-        hz.name,
-        records.resource_record_sets,
-    ))
-}
+    let mut export = HostedZoneExport::new(hz.id, hz.name, record_sets, dnssec);
+    if let Some(geoip_db) = geoip_db {
+        geoip_db.enrich_export(&mut export);
+    }
 
-mod export {
-    use aws_sdk_route53::types;
-    use serde::Serialize;
+    Ok(export)
+}
 
-    #[derive(Debug, Serialize)]
-    pub struct HostedZoneExport {
-        id: String,
-        name: String,
-        record_sets: Vec<ResourceRecordSet>,
-    }
+/// Not every principal has `route53:GetDNSSEC`, and the call can fail for
+/// other reasons too; a missing DNSSEC snapshot shouldn't abort the whole
+/// export, so warn and fall back to an empty one.
+async fn get_dnssec(client: &aws_sdk_route53::Client, hosted_zone_id: &str) -> export::Dnssec {
+    let response = client
+        .get_dnssec()
+        .hosted_zone_id(hosted_zone_id)
+        .send()
+        .await;
 
-    impl HostedZoneExport {
-        pub fn new(id: String, name: String, record_sets: Vec<types::ResourceRecordSet>) -> Self {
-            Self {
-                id,
-                name,
-                record_sets: record_sets.into_iter().map(Into::into).collect(),
-            }
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => {
+            eprintln!("Warning: failed to fetch DNSSEC status for {hosted_zone_id}: {err}");
+            return export::Dnssec::default();
         }
-    }
+    };
 
-    #[derive(Debug, Serialize)]
-    struct ResourceRecordSet {
-        name: String,
-        r#type: String,
-        set_identifier: Option<String>,
-        weight: Option<i64>,
-        region: Option<String>,
-        geo_location: Option<GeoLocation>,
-        failover: Option<String>,
-        multi_value_answer: Option<bool>,
-        ttl: Option<i64>,
-        resource_records: Option<Vec<ResourceRecord>>,
-        alias_target: Option<AliasTarget>,
-        health_check_id: Option<String>,
-        traffic_policy_instance_id: Option<String>,
-        cidr_routing_config: Option<CidrRoutingConfig>,
-        geo_proximity_location: Option<GeoProximityLocation>,
-    }
-    impl From<types::ResourceRecordSet> for ResourceRecordSet {
-        fn from(value: types::ResourceRecordSet) -> Self {
-            Self {
-                name: value.name,
-                r#type: value.r#type.as_str().to_owned(),
-                set_identifier: value.set_identifier,
-                weight: value.weight,
-                region: value.region.map(|r| r.as_str().to_owned()),
-                geo_location: value.geo_location.map(Into::into),
-                failover: value.failover.map(|f| f.as_str().to_owned()),
-                multi_value_answer: value.multi_value_answer,
-                ttl: value.ttl,
-                resource_records: value
-                    .resource_records
-                    .map(|records| records.into_iter().map(Into::into).collect()),
-                alias_target: value.alias_target.map(Into::into),
-                health_check_id: value.health_check_id,
-                traffic_policy_instance_id: value.traffic_policy_instance_id,
-                cidr_routing_config: value.cidr_routing_config.map(Into::into),
-                geo_proximity_location: value.geo_proximity_location.map(Into::into),
-            }
-        }
+    export::Dnssec {
+        status: response.status.map(Into::into),
+        key_signing_keys: response
+            .key_signing_keys
+            .unwrap_or_default()
+            .into_iter()
+            .map(Into::into)
+            .collect(),
     }
+}
 
-    #[derive(Debug, Serialize)]
-    struct GeoLocation {
-        continent_code: Option<String>,
-        country_code: Option<String>,
-        subdivision_code: Option<String>,
-    }
-    impl From<types::GeoLocation> for GeoLocation {
-        fn from(value: types::GeoLocation) -> Self {
-            Self {
-                continent_code: value.continent_code,
-                country_code: value.country_code,
-                subdivision_code: value.subdivision_code,
-            }
-        }
-    }
+/// `ListHostedZones` caps each page at 100 zones; keep following `next_marker`
+/// until `is_truncated` is false so large accounts aren't silently truncated.
+async fn list_all_hosted_zones(client: &aws_sdk_route53::Client) -> Result<Vec<types::HostedZone>> {
+    let mut hosted_zones = Vec::new();
+    let mut marker = None;
 
-    #[derive(Debug, Serialize)]
-    struct ResourceRecord {
-        value: String,
-    }
-    impl From<types::ResourceRecord> for ResourceRecord {
-        fn from(value: types::ResourceRecord) -> Self {
-            Self { value: value.value }
+    loop {
+        let mut request = client.list_hosted_zones();
+        if let Some(marker) = &marker {
+            request = request.marker(marker);
         }
-    }
+        let response = request.send().await?;
 
-    #[derive(Debug, Serialize)]
-    struct AliasTarget {
-        hosted_zone_id: String,
-        dns_name: String,
-        evaluate_target_health: bool,
-    }
-    impl From<types::AliasTarget> for AliasTarget {
-        fn from(value: types::AliasTarget) -> Self {
-            Self {
-                hosted_zone_id: value.hosted_zone_id,
-                dns_name: value.dns_name,
-                evaluate_target_health: value.evaluate_target_health,
-            }
-        }
-    }
+        hosted_zones.extend(response.hosted_zones().to_owned());
 
-    #[derive(Debug, Serialize)]
-    struct CidrRoutingConfig {
-        collection_id: String,
-        location_name: String,
-    }
-    impl From<types::CidrRoutingConfig> for CidrRoutingConfig {
-        fn from(value: types::CidrRoutingConfig) -> Self {
-            Self {
-                collection_id: value.collection_id,
-                location_name: value.location_name,
-            }
+        if !response.is_truncated() {
+            break;
         }
+        marker = response.next_marker().map(str::to_owned);
     }
 
-    #[derive(Debug, Serialize)]
-    struct GeoProximityLocation {
-        aws_region: Option<String>,
-        local_zone_group: Option<String>,
-        coordinates: Option<Coordinates>,
-        bias: Option<i32>,
-    }
-    impl From<types::GeoProximityLocation> for GeoProximityLocation {
-        fn from(value: types::GeoProximityLocation) -> Self {
-            Self {
-                aws_region: value.aws_region,
-                local_zone_group: value.local_zone_group,
-                coordinates: value.coordinates.map(Into::into),
-                bias: value.bias,
+    Ok(hosted_zones)
+}
+
+/// `ListResourceRecordSets` caps each page at 100 record sets; keep following
+/// the `start_record_*` markers until `is_truncated` is false so zones with
+/// more records aren't silently truncated.
+async fn list_all_resource_record_sets(
+    client: &aws_sdk_route53::Client,
+    hosted_zone_id: &str,
+) -> Result<Vec<types::ResourceRecordSet>> {
+    let mut record_sets = Vec::new();
+    let mut next = None;
+
+    loop {
+        let mut request = client
+            .list_resource_record_sets()
+            .hosted_zone_id(hosted_zone_id);
+        if let Some((name, r#type, identifier)) = next.clone() {
+            request = request.start_record_name(name).start_record_type(r#type);
+            if let Some(identifier) = identifier {
+                request = request.start_record_identifier(identifier);
             }
         }
-    }
+        let response = request.send().await?;
 
-    #[derive(Debug, Serialize)]
-    struct Coordinates {
-        latitude: String,
-        longitude: String,
-    }
-    impl From<types::Coordinates> for Coordinates {
-        fn from(value: types::Coordinates) -> Self {
-            Self {
-                latitude: value.latitude,
-                longitude: value.longitude,
-            }
+        record_sets.extend(response.resource_record_sets);
+
+        if !response.is_truncated {
+            break;
         }
+        next = Some((
+            response
+                .next_record_name
+                .ok_or_else(|| eyre!("truncated ListResourceRecordSets response missing next_record_name"))?,
+            response
+                .next_record_type
+                .ok_or_else(|| eyre!("truncated ListResourceRecordSets response missing next_record_type"))?,
+            response.next_record_identifier,
+        ));
     }
+
+    Ok(record_sets)
 }
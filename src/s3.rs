@@ -0,0 +1,104 @@
+use color_eyre::{eyre::eyre, Result};
+use flate2::{write::GzEncoder, Compression};
+use std::io::Write;
+
+/// An `s3://bucket/prefix` export destination, parsed out of the `--export`
+/// path so exports can be archived centrally instead of written locally.
+pub struct S3Destination {
+    pub bucket: String,
+    pub prefix: Option<String>,
+}
+
+impl S3Destination {
+    pub fn parse(destination: &str) -> Option<Self> {
+        let rest = destination.strip_prefix("s3://")?;
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+
+        Some(Self {
+            bucket: bucket.to_owned(),
+            prefix: (!prefix.is_empty()).then(|| prefix.trim_end_matches('/').to_owned()),
+        })
+    }
+
+    fn key(&self, zone_name: &str, extension: &str) -> String {
+        let timestamp = time::OffsetDateTime::now_utc().unix_timestamp();
+        let file_name = format!("{}-{timestamp}.{extension}", zone_name.trim_end_matches('.'));
+
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}/{file_name}"),
+            None => file_name,
+        }
+    }
+}
+
+pub async fn upload(
+    aws_config: &aws_config::SdkConfig,
+    destination: &S3Destination,
+    zone_name: &str,
+    data: String,
+    extension: &str,
+    compress: bool,
+) -> Result<()> {
+    let client = aws_sdk_s3::Client::new(aws_config);
+
+    let (body, extension) = if compress {
+        (gzip(data.as_bytes())?, format!("{extension}.gz"))
+    } else {
+        (data.into_bytes(), extension.to_owned())
+    };
+
+    let key = destination.key(zone_name, &extension);
+
+    client
+        .put_object()
+        .bucket(&destination.bucket)
+        .key(&key)
+        .body(body.into())
+        .send()
+        .await
+        .map_err(|err| eyre!("failed to upload s3://{}/{key}: {err}", destination.bucket))?;
+
+    println!(
+        "Successfully exported data to s3://{}/{key} 🎉",
+        destination.bucket
+    );
+
+    Ok(())
+}
+
+fn gzip(data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rejects_non_s3_destinations() {
+        assert!(S3Destination::parse("./route53-export.json").is_none());
+        assert!(S3Destination::parse("/tmp/route53-export.json").is_none());
+    }
+
+    #[test]
+    fn parse_splits_bucket_and_prefix() {
+        let dest = S3Destination::parse("s3://my-bucket/exports/prod").unwrap();
+        assert_eq!(dest.bucket, "my-bucket");
+        assert_eq!(dest.prefix.as_deref(), Some("exports/prod"));
+    }
+
+    #[test]
+    fn parse_bucket_only_has_no_prefix() {
+        let dest = S3Destination::parse("s3://my-bucket").unwrap();
+        assert_eq!(dest.bucket, "my-bucket");
+        assert_eq!(dest.prefix, None);
+    }
+
+    #[test]
+    fn parse_trims_trailing_slash_from_prefix() {
+        let dest = S3Destination::parse("s3://my-bucket/exports/").unwrap();
+        assert_eq!(dest.prefix.as_deref(), Some("exports"));
+    }
+}
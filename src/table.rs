@@ -0,0 +1,145 @@
+use color_eyre::Result;
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::export::{HostedZoneExport, ResourceRecordSet};
+
+/// One row per `ResourceRecordSet`, shared by the terminal summary table and
+/// the `--format csv` output so both stay in sync.
+#[derive(Tabled, Serialize)]
+pub struct SummaryRow {
+    pub name: String,
+    pub r#type: String,
+    pub ttl: String,
+    pub routing_policy: String,
+    pub values: String,
+}
+
+fn rows(export: &HostedZoneExport) -> Vec<SummaryRow> {
+    export.record_sets.iter().map(summary_row).collect()
+}
+
+fn summary_row(record: &ResourceRecordSet) -> SummaryRow {
+    let values = match &record.alias_target {
+        Some(alias) => alias.dns_name.clone(),
+        None => record
+            .resource_records
+            .as_ref()
+            .map(|records| {
+                records
+                    .iter()
+                    .map(|r| r.value.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default(),
+    };
+
+    SummaryRow {
+        name: record.name.clone(),
+        r#type: record.r#type.clone(),
+        ttl: record.ttl.map(|ttl| ttl.to_string()).unwrap_or_default(),
+        routing_policy: routing_policy(record).to_owned(),
+        values,
+    }
+}
+
+fn routing_policy(record: &ResourceRecordSet) -> &'static str {
+    if record.weight.is_some() {
+        "weighted"
+    } else if record.region.is_some() {
+        "latency"
+    } else if record.geo_location.is_some() {
+        "geolocation"
+    } else if record.failover.is_some() {
+        "failover"
+    } else if record.multi_value_answer.unwrap_or(false) {
+        "multivalue"
+    } else if record.cidr_routing_config.is_some() {
+        "cidr"
+    } else {
+        "simple"
+    }
+}
+
+/// Prints a summary table per zone so operators get an at-a-glance view
+/// without hand-parsing the export file.
+pub fn print_summary(exports: &[HostedZoneExport]) {
+    for export in exports {
+        println!("\n{} ({})", export.name, export.id);
+        println!("{}", tabled::Table::new(rows(export)));
+    }
+}
+
+pub fn render_csv(exports: &[HostedZoneExport]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for export in exports {
+        for row in rows(export) {
+            writer.serialize(row)?;
+        }
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routing_policy_defaults_to_simple() {
+        assert_eq!(routing_policy(&ResourceRecordSet::default()), "simple");
+    }
+
+    #[test]
+    fn routing_policy_prefers_weighted_over_other_fields() {
+        let record = ResourceRecordSet {
+            weight: Some(1),
+            region: Some("us-east-1".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(routing_policy(&record), "weighted");
+    }
+
+    #[test]
+    fn routing_policy_recognizes_each_policy() {
+        let latency = ResourceRecordSet {
+            region: Some("us-east-1".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(routing_policy(&latency), "latency");
+
+        let geolocation = ResourceRecordSet {
+            geo_location: Some(crate::export::GeoLocation {
+                continent_code: Some("EU".to_owned()),
+                country_code: None,
+                subdivision_code: None,
+                continent_name: None,
+                country_name: None,
+                subdivision_name: None,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(routing_policy(&geolocation), "geolocation");
+
+        let failover = ResourceRecordSet {
+            failover: Some("PRIMARY".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(routing_policy(&failover), "failover");
+
+        let cidr = ResourceRecordSet {
+            cidr_routing_config: Some(crate::export::CidrRoutingConfig {
+                collection_id: "collection".to_owned(),
+                location_name: "office".to_owned(),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(routing_policy(&cidr), "cidr");
+
+        let multivalue = ResourceRecordSet {
+            multi_value_answer: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(routing_policy(&multivalue), "multivalue");
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt::Write;
+
+use crate::export::HostedZoneExport;
+
+const DEFAULT_TTL: i64 = 300;
+
+/// Renders an export as an RFC 1035 master/zone file, for tooling that
+/// expects plain BIND zone files instead of our JSON export format.
+pub fn render(export: &HostedZoneExport) -> String {
+    let origin = export.name.trim_end_matches('.');
+    let mut out = String::new();
+
+    writeln!(out, "$ORIGIN {origin}.").unwrap();
+    writeln!(out, "$TTL {DEFAULT_TTL}").unwrap();
+    writeln!(out).unwrap();
+
+    for record in &export.record_sets {
+        let name = relative_name(&record.name, origin);
+        let ttl = record.ttl.unwrap_or(DEFAULT_TTL);
+
+        if let Some(alias) = &record.alias_target {
+            writeln!(
+                out,
+                "; {name} {ttl} IN {r#type} is an alias to {target}, which BIND cannot express natively",
+                r#type = record.r#type,
+                target = alias.dns_name,
+            )
+            .unwrap();
+            continue;
+        }
+
+        let line_prefix = format!("{name} {ttl} IN {}", record.r#type);
+        let Some(rdata) = rdata(record, &line_prefix) else {
+            continue;
+        };
+
+        write!(out, "{line_prefix} {rdata}").unwrap();
+        if let Some(comment) = routing_policy_comment(record) {
+            write!(out, " ; {comment}").unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    out
+}
+
+fn relative_name(name: &str, origin: &str) -> String {
+    let name = name.trim_end_matches('.');
+    if name == origin {
+        "@".to_owned()
+    } else if let Some(prefix) = name.strip_suffix(&format!(".{origin}")) {
+        prefix.to_owned()
+    } else {
+        format!("{name}.")
+    }
+}
+
+/// Each value of a multi-value record set gets its own full line, since a
+/// zone-file continuation line still needs its own type field.
+fn rdata(record: &crate::export::ResourceRecordSet, line_prefix: &str) -> Option<String> {
+    let records = record.resource_records.as_ref()?;
+
+    Some(
+        records
+            .iter()
+            .map(|r| format_value(&record.r#type, &r.value))
+            .collect::<Vec<_>>()
+            .join(&format!("\n{line_prefix} ")),
+    )
+}
+
+fn format_value(r#type: &str, value: &str) -> String {
+    match r#type {
+        "TXT" => quote_txt(value),
+        // MX ("priority exchange") and SRV ("priority weight port target")
+        // rdata is already the full space-separated value Route53 returns,
+        // so there's nothing left to assemble here.
+        _ => value.to_owned(),
+    }
+}
+
+fn quote_txt(value: &str) -> String {
+    if value.starts_with('"') && value.ends_with('"') {
+        value.to_owned()
+    } else {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    }
+}
+
+fn routing_policy_comment(record: &crate::export::ResourceRecordSet) -> Option<String> {
+    if record.weight.is_some() {
+        Some(format!(
+            "weighted routing, weight={}",
+            record.weight.unwrap()
+        ))
+    } else if let Some(region) = &record.region {
+        Some(format!("latency-based routing, region={region}"))
+    } else if let Some(geo) = &record.geo_location {
+        Some(format!(
+            "geolocation routing, continent={:?} country={:?} subdivision={:?}",
+            geo.continent_code, geo.country_code, geo.subdivision_code
+        ))
+    } else if let Some(geo_proximity) = &record.geo_proximity_location {
+        Some(format!(
+            "geoproximity routing, region={:?} bias={:?}",
+            geo_proximity.aws_region, geo_proximity.bias
+        ))
+    } else if let Some(failover) = &record.failover {
+        Some(format!("failover routing, failover={failover}"))
+    } else if record.multi_value_answer.unwrap_or(false) {
+        Some("multi-value answer routing".to_owned())
+    } else if let Some(cidr) = &record.cidr_routing_config {
+        Some(format!(
+            "CIDR routing, collection={} location={}",
+            cidr.collection_id, cidr.location_name
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ResourceRecordSet;
+
+    #[test]
+    fn relative_name_at_apex_is_at_sign() {
+        assert_eq!(relative_name("example.com.", "example.com"), "@");
+    }
+
+    #[test]
+    fn relative_name_strips_origin_suffix() {
+        assert_eq!(relative_name("www.example.com.", "example.com"), "www");
+    }
+
+    #[test]
+    fn relative_name_outside_origin_is_fully_qualified() {
+        assert_eq!(relative_name("other.org.", "example.com"), "other.org.");
+    }
+
+    #[test]
+    fn quote_txt_wraps_unquoted_values() {
+        assert_eq!(quote_txt("v=spf1 -all"), "\"v=spf1 -all\"");
+    }
+
+    #[test]
+    fn quote_txt_leaves_already_quoted_values() {
+        assert_eq!(quote_txt("\"v=spf1 -all\""), "\"v=spf1 -all\"");
+    }
+
+    #[test]
+    fn quote_txt_escapes_embedded_quotes() {
+        assert_eq!(quote_txt("say \"hi\""), "\"say \\\"hi\\\"\"");
+    }
+
+    #[test]
+    fn routing_policy_comment_defaults_to_none_for_simple_records() {
+        let record = ResourceRecordSet::default();
+        assert_eq!(routing_policy_comment(&record), None);
+    }
+
+    #[test]
+    fn routing_policy_comment_describes_weighted_routing() {
+        let record = ResourceRecordSet {
+            weight: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(
+            routing_policy_comment(&record),
+            Some("weighted routing, weight=10".to_owned())
+        );
+    }
+
+    #[test]
+    fn routing_policy_comment_describes_failover_routing() {
+        let record = ResourceRecordSet {
+            failover: Some("PRIMARY".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            routing_policy_comment(&record),
+            Some("failover routing, failover=PRIMARY".to_owned())
+        );
+    }
+}